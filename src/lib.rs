@@ -1,7 +1,46 @@
 use gargoyle::{Action, Monitor};
 
 use log::info;
-use sysinfo::System;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+pub mod log_watch;
+#[cfg(feature = "systemd")]
+pub mod systemd;
+
+pub use log_watch::LogWatch;
+use sysinfo::{Process, ProcessStatus, System};
+
+/// `true` if a process in `status` should still be considered alive.
+fn is_running_status(status: ProcessStatus) -> bool {
+    !matches!(status, ProcessStatus::Zombie | ProcessStatus::Dead | ProcessStatus::Stop)
+}
+
+/// A short description of an unhealthy process status, for diagnostics.
+fn unhealthy_status_description(status: ProcessStatus) -> String {
+    match status {
+        ProcessStatus::Zombie => "defunct (zombie)".to_string(),
+        ProcessStatus::Dead => "dead".to_string(),
+        ProcessStatus::Stop => "stopped".to_string(),
+        other => format!("unhealthy ({other})"),
+    }
+}
+
+/// Reports whether at least one of `processes` is in a running/sleeping state,
+/// along with a diagnostic description of the first unhealthy one found.
+fn health_of<'a>(processes: impl Iterator<Item = &'a Process>) -> (bool, Option<String>) {
+    let mut any_running = false;
+    let mut unhealthy = None;
+    for process in processes {
+        let status = process.status();
+        if is_running_status(status) {
+            any_running = true;
+        } else {
+            unhealthy.get_or_insert_with(|| unhealthy_status_description(status));
+        }
+    }
+    (any_running, unhealthy)
+}
 
 /// Check the local system for a service by name.
 ///
@@ -31,6 +70,7 @@ use sysinfo::System;
 pub struct Service {
     pub process_name: String,
     system: System,
+    treat_zombie_as_down: bool,
 }
 
 /// Check the local system for a service by exact name.
@@ -61,6 +101,7 @@ pub struct Service {
 pub struct ExactService {
     pub process_name: String,
     system: System,
+    treat_zombie_as_down: bool,
 }
 
 impl Service {
@@ -68,8 +109,17 @@ impl Service {
         Service {
             process_name: process_name.to_string(),
             system: System::new(),
+            treat_zombie_as_down: false,
         }
     }
+
+    /// When `true`, a process stuck in the zombie/defunct (or dead/stopped) state
+    /// is treated as down even though it still appears in the process table.
+    /// Defaults to `false` to preserve the original existence-only behavior.
+    pub fn treat_zombie_as_down(mut self, treat_zombie_as_down: bool) -> Service {
+        self.treat_zombie_as_down = treat_zombie_as_down;
+        self
+    }
 }
 
 impl ExactService {
@@ -77,23 +127,50 @@ impl ExactService {
         ExactService {
             process_name: process_name.to_string(),
             system: System::new(),
+            treat_zombie_as_down: false,
         }
     }
+
+    /// When `true`, a process stuck in the zombie/defunct (or dead/stopped) state
+    /// is treated as down even though it still appears in the process table.
+    /// Defaults to `false` to preserve the original existence-only behavior.
+    pub fn treat_zombie_as_down(mut self, treat_zombie_as_down: bool) -> ExactService {
+        self.treat_zombie_as_down = treat_zombie_as_down;
+        self
+    }
 }
 
+impl StateMatcher for Service {}
+impl StateMatcher for ExactService {}
+impl StateMatcher for ProcessResource {}
+
 /// Checks the local system for a service by name.
 impl Monitor for Service {
     fn check(&mut self) -> Action {
         self.system.refresh_processes();
-        if self.system.processes_by_name(&self.process_name).next().is_none() {
+        let mut processes = self.system.processes_by_name(&self.process_name).peekable();
+        if processes.peek().is_none() {
             info!("{} is down", self.process_name);
-            Action::Notify {
+            return Action::Notify {
                 diagnostic: Some(format!("{} is down", self.process_name))
+            };
+        }
+
+        if self.treat_zombie_as_down {
+            let (any_running, unhealthy) = health_of(processes);
+            if !any_running {
+                let diagnostic = format!(
+                    "{} is {}",
+                    self.process_name,
+                    unhealthy.unwrap_or_else(|| "unhealthy".to_string())
+                );
+                info!("{diagnostic}");
+                return Action::Notify { diagnostic: Some(diagnostic) };
             }
-        } else {
-            info!("{} is up", self.process_name);
-            Action::Nothing
         }
+
+        info!("{} is up", self.process_name);
+        Action::Nothing
     }
 }
 
@@ -101,15 +178,546 @@ impl Monitor for Service {
 impl Monitor for ExactService {
     fn check(&mut self) -> Action {
         self.system.refresh_processes();
-        if self.system.processes_by_exact_name(&self.process_name).next().is_none() {
+        let mut processes = self.system.processes_by_exact_name(&self.process_name).peekable();
+        if processes.peek().is_none() {
             info!("{} is down", self.process_name);
-            Action::Notify {
+            return Action::Notify {
                 diagnostic: Some(format!("{} is down", self.process_name))
+            };
+        }
+
+        if self.treat_zombie_as_down {
+            let (any_running, unhealthy) = health_of(processes);
+            if !any_running {
+                let diagnostic = format!(
+                    "{} is {}",
+                    self.process_name,
+                    unhealthy.unwrap_or_else(|| "unhealthy".to_string())
+                );
+                info!("{diagnostic}");
+                return Action::Notify { diagnostic: Some(diagnostic) };
             }
+        }
+
+        info!("{} is up", self.process_name);
+        Action::Nothing
+    }
+}
+
+/// Check the local system for a process exceeding a CPU and/or memory ceiling.
+///
+/// # Example
+///
+/// ```
+/// # use std::thread::sleep;
+/// # use std::time::Duration;
+/// use gargoyle::{modules::{monitor, notify}, Schedule};
+/// let process_name = "nginx";
+/// let resource_monitor = monitor::ProcessResource::new(process_name)
+///    .with_cpu_percent(80.0)
+///    .with_memory_bytes(512 * 1024 * 1024);
+/// let stdout_notifier = notify::Stdout;
+/// let mut schedule = Schedule::new();
+/// schedule.add(
+///    &format!("The Gargoyle has detected that {process_name} is over its resource limits"),
+///    &format!("The Gargoyle has detected that {process_name} is back within its resource limits"),
+///    Duration::from_secs(30),
+///    &resource_monitor,
+///    &stdout_notifier,
+/// );
+///
+/// loop {
+///    schedule.run();
+///    sleep(Duration::from_millis(100));
+/// }
+/// ```
+pub struct ProcessResource {
+    pub process_name: String,
+    cpu_percent: Option<f32>,
+    memory_bytes: Option<u64>,
+    system: System,
+    warmed_up: bool,
+}
+
+impl ProcessResource {
+    pub fn new(process_name: &str) -> ProcessResource {
+        ProcessResource {
+            process_name: process_name.to_string(),
+            cpu_percent: None,
+            memory_bytes: None,
+            system: System::new(),
+            warmed_up: false,
+        }
+    }
+
+    /// Notify when the process' aggregate CPU usage exceeds `percent` (e.g. `80.0` for 80%).
+    pub fn with_cpu_percent(mut self, percent: f32) -> ProcessResource {
+        self.cpu_percent = Some(percent);
+        self
+    }
+
+    /// Notify when the process' aggregate resident memory (RSS) exceeds `bytes`.
+    pub fn with_memory_bytes(mut self, bytes: u64) -> ProcessResource {
+        self.memory_bytes = Some(bytes);
+        self
+    }
+}
+
+/// Checks the local system for a process exceeding its configured CPU/memory ceilings.
+impl Monitor for ProcessResource {
+    fn check(&mut self) -> Action {
+        self.system.refresh_processes();
+
+        let mut found = false;
+        let mut total_cpu = 0.0;
+        let mut total_memory = 0;
+        for process in self.system.processes_by_name(&self.process_name) {
+            found = true;
+            total_cpu += process.cpu_usage();
+            total_memory += process.memory();
+        }
+
+        if !found {
+            info!("{} is down", self.process_name);
+            return Action::Notify {
+                diagnostic: Some(format!("{} is down", self.process_name)),
+            };
+        }
+
+        // `cpu_usage()` is only meaningful once it has a prior refresh to diff
+        // against, so the very first sample is skipped rather than blocking
+        // this call on `MINIMUM_CPU_UPDATE_INTERVAL`; every later call is
+        // naturally spaced far enough apart by the schedule's own tick
+        // interval for sysinfo's cached sample to be accurate.
+        let cpu_sample_ready = self.warmed_up;
+        self.warmed_up = true;
+
+        if cpu_sample_ready {
+            if let Some(limit) = self.cpu_percent {
+                if total_cpu > limit {
+                    let diagnostic = format!(
+                        "{} exceeded {limit:.1}% CPU (now {total_cpu:.1}%)",
+                        self.process_name
+                    );
+                    info!("{diagnostic}");
+                    return Action::Notify { diagnostic: Some(diagnostic) };
+                }
+            }
+        }
+
+        if let Some(limit) = self.memory_bytes {
+            if total_memory > limit {
+                let diagnostic = format!(
+                    "{} exceeded {} MiB RSS (now {} MiB)",
+                    self.process_name,
+                    limit / (1024 * 1024),
+                    total_memory / (1024 * 1024),
+                );
+                info!("{diagnostic}");
+                return Action::Notify { diagnostic: Some(diagnostic) };
+            }
+        }
+
+        info!("{} is within its resource limits", self.process_name);
+        Action::Nothing
+    }
+}
+
+/// The externally-reported health of a debounced monitor.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum State {
+    Up,
+    Down,
+}
+
+/// Lets a `Monitor` classify its own `Action` output as an `Up`/`Down` state for
+/// `Debounced` to track. The default classification treats `Action::Notify` as
+/// down and `Action::Nothing` as up, which is correct for every monitor in this
+/// crate; opt a monitor in with `impl StateMatcher for MyMonitor {}` and only
+/// override `state` if `Action::Notify` alone isn't a reliable down signal.
+pub trait StateMatcher {
+    fn state(&self, action: &Action) -> State {
+        match action {
+            Action::Notify { .. } => State::Down,
+            Action::Nothing => State::Up,
+        }
+    }
+}
+
+/// Wraps a `Monitor` with hysteresis, so a state only flips once it has been
+/// observed for a configurable number of consecutive checks. This suppresses
+/// notification storms from services that flap during a restart.
+///
+/// # Example
+///
+/// ```
+/// # use std::thread::sleep;
+/// # use std::time::Duration;
+/// use gargoyle::{modules::{monitor, notify}, Schedule};
+/// let process_name = "nginx";
+/// let service_monitor = monitor::Service::new(process_name);
+/// let debounced_monitor = monitor::Debounced::new(service_monitor, 3, 2);
+/// let stdout_notifier = notify::Stdout;
+/// let mut schedule = Schedule::new();
+/// schedule.add(
+///    &format!("The Gargoyle has detected that {process_name} has gone down"),
+///    &format!("The Gargoyle has detected that {process_name} has recovered"),
+///    Duration::from_secs(30),
+///    &debounced_monitor,
+///    &stdout_notifier,
+/// );
+///
+/// loop {
+///    schedule.run();
+///    sleep(Duration::from_millis(100));
+/// }
+/// ```
+pub struct Debounced<M: Monitor + StateMatcher> {
+    inner: M,
+    down_after: usize,
+    up_after: usize,
+    state: State,
+    pending: State,
+    run_length: usize,
+    diagnostic: Option<String>,
+}
+
+impl<M: Monitor + StateMatcher> Debounced<M> {
+    /// Wrap `inner`, requiring `down_after` consecutive failing checks before
+    /// reporting down and `up_after` consecutive passing checks before
+    /// reporting recovery.
+    pub fn new(inner: M, down_after: usize, up_after: usize) -> Debounced<M> {
+        Debounced {
+            inner,
+            down_after: down_after.max(1),
+            up_after: up_after.max(1),
+            state: State::Up,
+            pending: State::Up,
+            run_length: 0,
+            diagnostic: None,
+        }
+    }
+
+    fn threshold(&self, state: State) -> usize {
+        match state {
+            State::Up => self.up_after,
+            State::Down => self.down_after,
+        }
+    }
+}
+
+/// Checks the inner monitor and only reports a transition once it has held for
+/// the configured number of consecutive checks.
+impl<M: Monitor + StateMatcher> Monitor for Debounced<M> {
+    fn check(&mut self) -> Action {
+        let action = self.inner.check();
+        let raw = self.inner.state(&action);
+        if let Action::Notify { diagnostic } = &action {
+            self.diagnostic = diagnostic.clone();
+        }
+
+        if raw == self.pending {
+            self.run_length += 1;
         } else {
-            info!("{} is up", self.process_name);
-            Action::Nothing
+            self.pending = raw;
+            self.run_length = 1;
+        }
+
+        if self.state != self.pending && self.run_length >= self.threshold(self.pending) {
+            self.state = self.pending;
+        }
+
+        match self.state {
+            State::Down => Action::Notify { diagnostic: self.diagnostic.clone() },
+            State::Up => Action::Nothing,
+        }
+    }
+}
+
+/// Describes how to attempt to recover a service once it's been detected as
+/// down: the recovery command to run, a cap on consecutive attempts, and a
+/// cooldown between them so a crash-looping service isn't restarted every tick.
+pub struct Remediation {
+    pub command: String,
+    pub max_attempts: u32,
+    pub backoff: Duration,
+}
+
+impl Remediation {
+    pub fn new(command: &str, max_attempts: u32, backoff: Duration) -> Remediation {
+        Remediation {
+            command: command.to_string(),
+            max_attempts,
+            backoff,
+        }
+    }
+}
+
+/// Wraps a `Monitor` so that, on a transition to down, a recovery command runs
+/// before the failure is reported, turning passive alerting into self-healing
+/// supervision. Attempts are capped at `remediation.max_attempts` and spaced
+/// out by `remediation.backoff`; attempts resume once the inner monitor
+/// recovers.
+///
+/// # Example
+///
+/// ```
+/// # use std::thread::sleep;
+/// # use std::time::Duration;
+/// use gargoyle::{modules::{monitor, notify}, Schedule};
+/// let process_name = "nginx";
+/// let service_monitor = monitor::Service::new(process_name);
+/// let remediation = monitor::Remediation::new(
+///    "systemctl restart nginx",
+///    3,
+///    Duration::from_secs(60),
+/// );
+/// let remediated_monitor = monitor::Remediated::new(service_monitor, remediation);
+/// let stdout_notifier = notify::Stdout;
+/// let mut schedule = Schedule::new();
+/// schedule.add(
+///    &format!("The Gargoyle has detected that {process_name} has gone down"),
+///    &format!("The Gargoyle has detected that {process_name} has recovered"),
+///    Duration::from_secs(30),
+///    &remediated_monitor,
+///    &stdout_notifier,
+/// );
+///
+/// loop {
+///    schedule.run();
+///    sleep(Duration::from_millis(100));
+/// }
+/// ```
+pub struct Remediated<M: Monitor> {
+    inner: M,
+    remediation: Remediation,
+    was_down: bool,
+    attempts: u32,
+    last_attempt: Option<Instant>,
+}
+
+impl<M: Monitor> Remediated<M> {
+    pub fn new(inner: M, remediation: Remediation) -> Remediated<M> {
+        Remediated {
+            inner,
+            remediation,
+            was_down: false,
+            attempts: 0,
+            last_attempt: None,
         }
     }
+
+    /// Run the recovery command in a shell and summarize its outcome.
+    fn run_remediation(&self) -> String {
+        match Command::new("sh").arg("-c").arg(&self.remediation.command).output() {
+            Ok(output) => format!(
+                "ran `{}`, exited with {}; stdout: {}; stderr: {}",
+                self.remediation.command,
+                output.status,
+                String::from_utf8_lossy(&output.stdout).trim(),
+                String::from_utf8_lossy(&output.stderr).trim(),
+            ),
+            Err(err) => format!("failed to run `{}`: {err}", self.remediation.command),
+        }
+    }
+}
+
+impl<M: Monitor> StateMatcher for Remediated<M> {}
+
+/// Checks the inner monitor and, on a transition to down, attempts remediation
+/// (subject to `max_attempts`/`backoff`) before reporting the failure.
+impl<M: Monitor> Monitor for Remediated<M> {
+    fn check(&mut self) -> Action {
+        let action = self.inner.check();
+        let Action::Notify { diagnostic } = &action else {
+            self.was_down = false;
+            self.attempts = 0;
+            self.last_attempt = None;
+            return action;
+        };
+
+        if !self.was_down {
+            self.was_down = true;
+            self.attempts = 0;
+            self.last_attempt = None;
+        }
+
+        let backoff_elapsed = self
+            .last_attempt
+            .map_or(true, |attempted_at| attempted_at.elapsed() >= self.remediation.backoff);
+
+        if self.attempts >= self.remediation.max_attempts || !backoff_elapsed {
+            return action;
+        }
+
+        self.attempts += 1;
+        self.last_attempt = Some(Instant::now());
+        let outcome = self.run_remediation();
+        let combined = format!(
+            "{}; remediation attempt {}/{}: {outcome}",
+            diagnostic.clone().unwrap_or_default(),
+            self.attempts,
+            self.remediation.max_attempts,
+        );
+        info!("{combined}");
+        Action::Notify { diagnostic: Some(combined) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// A `Monitor` that replays a canned sequence of results, one per `check()`
+    /// call, then reports healthy forever once exhausted.
+    struct FakeMonitor {
+        results: VecDeque<Action>,
+    }
+
+    impl FakeMonitor {
+        fn new(results: Vec<Action>) -> FakeMonitor {
+            FakeMonitor { results: results.into() }
+        }
+    }
+
+    impl Monitor for FakeMonitor {
+        fn check(&mut self) -> Action {
+            self.results.pop_front().unwrap_or(Action::Nothing)
+        }
+    }
+
+    impl StateMatcher for FakeMonitor {}
+
+    fn down(diagnostic: &str) -> Action {
+        Action::Notify { diagnostic: Some(diagnostic.to_string()) }
+    }
+
+    fn is_down(action: &Action) -> bool {
+        matches!(action, Action::Notify { .. })
+    }
+
+    #[test]
+    fn is_running_status_classifies_known_statuses() {
+        assert!(is_running_status(ProcessStatus::Run));
+        assert!(is_running_status(ProcessStatus::Sleep));
+        assert!(!is_running_status(ProcessStatus::Zombie));
+        assert!(!is_running_status(ProcessStatus::Dead));
+        assert!(!is_running_status(ProcessStatus::Stop));
+    }
+
+    #[test]
+    fn unhealthy_status_description_names_the_status() {
+        assert_eq!(unhealthy_status_description(ProcessStatus::Zombie), "defunct (zombie)");
+        assert_eq!(unhealthy_status_description(ProcessStatus::Dead), "dead");
+        assert_eq!(unhealthy_status_description(ProcessStatus::Stop), "stopped");
+    }
+
+    #[test]
+    fn health_of_reports_healthy_for_a_real_running_process() {
+        let mut system = System::new_all();
+        system.refresh_processes();
+        let current_pid = std::process::id();
+        let process = system
+            .processes()
+            .values()
+            .find(|process| process.pid().as_u32() == current_pid)
+            .expect("the current process should be visible to sysinfo");
+
+        let (any_running, unhealthy) = health_of(std::iter::once(process));
+        assert!(any_running);
+        assert!(unhealthy.is_none());
+    }
+
+    fn diagnostic_of(action: &Action) -> Option<String> {
+        match action {
+            Action::Notify { diagnostic } => diagnostic.clone(),
+            Action::Nothing => None,
+        }
+    }
+
+    #[test]
+    fn debounced_waits_for_down_after_consecutive_failures() {
+        let inner = FakeMonitor::new(vec![down("x"), down("x"), down("x"), down("x")]);
+        let mut debounced = Debounced::new(inner, 3, 2);
+        assert!(!is_down(&debounced.check()));
+        assert!(!is_down(&debounced.check()));
+        assert!(is_down(&debounced.check()));
+        assert!(is_down(&debounced.check()));
+    }
+
+    #[test]
+    fn debounced_waits_for_up_after_consecutive_successes() {
+        let inner = FakeMonitor::new(vec![
+            down("x"), down("x"), down("x"),
+            Action::Nothing, Action::Nothing,
+        ]);
+        let mut debounced = Debounced::new(inner, 3, 2);
+        for _ in 0..3 {
+            debounced.check();
+        }
+        assert!(is_down(&debounced.check()));
+        assert!(!is_down(&debounced.check()));
+    }
+
+    #[test]
+    fn debounced_resets_run_length_on_disagreeing_sample() {
+        let inner = FakeMonitor::new(vec![
+            down("x"), down("x"), Action::Nothing,
+            down("x"), down("x"), down("x"),
+        ]);
+        let mut debounced = Debounced::new(inner, 3, 2);
+        assert!(!is_down(&debounced.check())); // down run-length 1
+        assert!(!is_down(&debounced.check())); // down run-length 2
+        assert!(!is_down(&debounced.check())); // up resets the run-length
+        assert!(!is_down(&debounced.check())); // down run-length 1 (post-reset)
+        assert!(!is_down(&debounced.check())); // down run-length 2
+        assert!(is_down(&debounced.check())); // down run-length 3: flips
+    }
+
+    #[test]
+    fn remediated_stops_after_max_attempts() {
+        let inner = FakeMonitor::new(vec![down("x"), down("x"), down("x")]);
+        let remediation = Remediation::new("true", 2, Duration::ZERO);
+        let mut remediated = Remediated::new(inner, remediation);
+
+        let first = remediated.check();
+        assert!(diagnostic_of(&first).unwrap().contains("remediation attempt 1/2"));
+
+        let second = remediated.check();
+        assert!(diagnostic_of(&second).unwrap().contains("remediation attempt 2/2"));
+
+        let third = remediated.check();
+        assert!(!diagnostic_of(&third).unwrap().contains("remediation attempt"));
+    }
+
+    #[test]
+    fn remediated_gates_retries_on_backoff() {
+        let inner = FakeMonitor::new(vec![down("x"), down("x")]);
+        let remediation = Remediation::new("true", 5, Duration::from_secs(9999));
+        let mut remediated = Remediated::new(inner, remediation);
+
+        let first = remediated.check();
+        assert!(diagnostic_of(&first).unwrap().contains("remediation attempt 1/5"));
+
+        // Backoff hasn't elapsed yet, so the second down tick shouldn't retry.
+        let second = remediated.check();
+        assert!(!diagnostic_of(&second).unwrap().contains("remediation attempt"));
+    }
+
+    #[test]
+    fn remediated_resets_attempts_on_recovery() {
+        let inner = FakeMonitor::new(vec![down("x"), Action::Nothing, down("x")]);
+        let remediation = Remediation::new("true", 1, Duration::ZERO);
+        let mut remediated = Remediated::new(inner, remediation);
+
+        let first = remediated.check();
+        assert!(diagnostic_of(&first).unwrap().contains("remediation attempt 1/1"));
+
+        assert!(!is_down(&remediated.check())); // recovers, resetting attempt count
+
+        let third = remediated.check();
+        assert!(diagnostic_of(&third).unwrap().contains("remediation attempt 1/1"));
+    }
 }
 
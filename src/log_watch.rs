@@ -0,0 +1,245 @@
+//! Tail a log file for a matching pattern or staleness, without re-reading the
+//! whole file each tick.
+
+use gargoyle::{Action, Monitor};
+
+use log::info;
+use notify::{recommended_watcher, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use regex::Regex;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::StateMatcher;
+
+/// Tail a log file and raise a notification when a line matches a configured
+/// pattern, or when the file stops growing for longer than a staleness
+/// timeout (a cheap liveness proxy for services that log heartbeats).
+///
+/// Only newly appended bytes are scanned each tick; the last read offset is
+/// remembered between checks. If the file shrinks below that offset (e.g. it
+/// was truncated or rotated), reading restarts from the beginning.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::thread::sleep;
+/// # use std::time::Duration;
+/// use gargoyle::{modules::notify, Schedule};
+/// use gargoyle_local_monitor::LogWatch;
+/// let log_monitor = LogWatch::new("/var/log/myapp.log", "FATAL|panic|OOM")
+///    .expect("failed to watch log file")
+///    .with_staleness_timeout(Duration::from_secs(300));
+/// let stdout_notifier = notify::Stdout;
+/// let mut schedule = Schedule::new();
+/// schedule.add(
+///    "The Gargoyle has detected a problem in myapp.log",
+///    "The Gargoyle has detected myapp.log is healthy again",
+///    Duration::from_secs(10),
+///    &log_monitor,
+///    &stdout_notifier,
+/// );
+///
+/// loop {
+///    schedule.run();
+///    sleep(Duration::from_millis(100));
+/// }
+/// ```
+pub struct LogWatch {
+    path: PathBuf,
+    pattern: Regex,
+    stale_after: Option<Duration>,
+    offset: u64,
+    last_growth: Instant,
+    events: Receiver<notify::Result<Event>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl LogWatch {
+    /// Watch `path`, notifying when an appended line matches `pattern`.
+    pub fn new(path: &str, pattern: &str) -> notify::Result<LogWatch> {
+        let (tx, rx) = channel();
+        let mut watcher = recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+        watcher.watch(Path::new(path), RecursiveMode::NonRecursive)?;
+
+        let offset = std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0);
+        Ok(LogWatch {
+            path: PathBuf::from(path),
+            pattern: Regex::new(pattern).map_err(|err| notify::Error::generic(&err.to_string()))?,
+            stale_after: None,
+            offset,
+            last_growth: Instant::now(),
+            events: rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// Also notify when the file hasn't grown in over `timeout`.
+    pub fn with_staleness_timeout(mut self, timeout: Duration) -> LogWatch {
+        self.stale_after = Some(timeout);
+        self
+    }
+
+    /// Read any newly appended bytes and return a diagnostic covering every
+    /// line that matches the configured pattern, if any. A single read can
+    /// contain more than one matching line (e.g. a burst of `FATAL` entries),
+    /// so every match in the batch is reported rather than just the first.
+    fn scan_new_lines(&mut self, length: u64) -> std::io::Result<Option<String>> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = Vec::with_capacity((length - self.offset) as usize);
+        file.read_to_end(&mut buf)?;
+        self.offset = length;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        let text = String::from_utf8_lossy(&buf);
+        let matches: Vec<String> = text
+            .lines()
+            .filter(|line| self.pattern.is_match(line))
+            .map(|line| format!("{line} (at {timestamp})"))
+            .collect();
+
+        if matches.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(format!(
+            "{} matched {} line(s): {}",
+            self.path.display(),
+            matches.len(),
+            matches.join(" | ")
+        )))
+    }
+}
+
+impl StateMatcher for LogWatch {}
+
+/// Checks for newly appended matching lines, then for staleness.
+impl Monitor for LogWatch {
+    fn check(&mut self) -> Action {
+        // Drain whatever the watcher has delivered since the last check
+        // without blocking; only bother stat-ing/reading the file if the
+        // watcher actually saw something change.
+        let mut changed = false;
+        while self.events.try_recv().is_ok() {
+            changed = true;
+        }
+
+        if changed {
+            let metadata = match std::fs::metadata(&self.path) {
+                Ok(metadata) => metadata,
+                Err(err) => {
+                    let diagnostic = format!("{} could not be read: {err}", self.path.display());
+                    info!("{diagnostic}");
+                    return Action::Notify { diagnostic: Some(diagnostic) };
+                }
+            };
+
+            let length = metadata.len();
+            if length < self.offset {
+                // Truncated or rotated out from under us: start over from scratch.
+                self.offset = 0;
+            }
+
+            if length > self.offset {
+                self.last_growth = Instant::now();
+                return match self.scan_new_lines(length) {
+                    Ok(Some(diagnostic)) => {
+                        info!("{diagnostic}");
+                        Action::Notify { diagnostic: Some(diagnostic) }
+                    }
+                    Ok(None) => Action::Nothing,
+                    Err(err) => {
+                        let diagnostic = format!("{} could not be read: {err}", self.path.display());
+                        info!("{diagnostic}");
+                        Action::Notify { diagnostic: Some(diagnostic) }
+                    }
+                };
+            }
+        }
+
+        if let Some(stale_after) = self.stale_after {
+            if self.last_growth.elapsed() >= stale_after {
+                let diagnostic = format!(
+                    "{} has not grown in over {stale_after:?}",
+                    self.path.display()
+                );
+                info!("{diagnostic}");
+                return Action::Notify { diagnostic: Some(diagnostic) };
+            }
+        }
+
+        Action::Nothing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static TEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_log_path() -> PathBuf {
+        let n = TEST_COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("gargoyle-log-watch-test-{}-{n}", std::process::id()))
+    }
+
+    /// Poll `check()` until it reports a match or `timeout` elapses.
+    fn wait_for_match(log_watch: &mut LogWatch, timeout: Duration) -> Option<String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Action::Notify { diagnostic } = log_watch.check() {
+                return diagnostic;
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn log_watch_reports_appended_matching_lines() {
+        let path = temp_log_path();
+        std::fs::write(&path, "startup ok\n").unwrap();
+        let mut log_watch = LogWatch::new(path.to_str().unwrap(), "FATAL").unwrap();
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "FATAL: disk full").unwrap();
+        file.flush().unwrap();
+
+        let diagnostic = wait_for_match(&mut log_watch, Duration::from_secs(2))
+            .expect("expected a match after appending a FATAL line");
+        assert!(diagnostic.contains("FATAL: disk full"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn log_watch_restarts_from_zero_after_truncation() {
+        let path = temp_log_path();
+        std::fs::write(&path, "a very long line that pushes the offset forward\n").unwrap();
+        let mut log_watch = LogWatch::new(path.to_str().unwrap(), "FATAL").unwrap();
+        assert!(wait_for_match(&mut log_watch, Duration::from_millis(300)).is_none());
+
+        // Truncate and write a short matching line; if the offset weren't
+        // reset to zero on a shrink, this read would be skipped or seek past it.
+        std::fs::write(&path, "FATAL\n").unwrap();
+
+        let diagnostic = wait_for_match(&mut log_watch, Duration::from_secs(2))
+            .expect("expected a match after truncation");
+        assert!(diagnostic.contains("FATAL"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
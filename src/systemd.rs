@@ -0,0 +1,199 @@
+//! systemd integration: monitor a unit's active-state and notify the service
+//! manager's watchdog. Gated behind the `systemd` feature so non-Linux builds
+//! are unaffected.
+
+use gargoyle::{Action, Monitor};
+
+use log::info;
+use std::env;
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+use std::process::Command;
+
+use crate::StateMatcher;
+
+/// Check a systemd unit's active-state, rather than matching a process name.
+///
+/// # Example
+///
+/// ```
+/// # use std::thread::sleep;
+/// # use std::time::Duration;
+/// use gargoyle::{modules::notify, Schedule};
+/// use gargoyle::modules::monitor::systemd::SystemdUnit;
+/// let unit = "nginx.service";
+/// let unit_monitor = SystemdUnit::new(unit);
+/// let stdout_notifier = notify::Stdout;
+/// let mut schedule = Schedule::new();
+/// schedule.add(
+///    &format!("The Gargoyle has detected that {unit} has gone down"),
+///    &format!("The Gargoyle has detected that {unit} has recovered"),
+///    Duration::from_secs(30),
+///    &unit_monitor,
+///    &stdout_notifier,
+/// );
+///
+/// loop {
+///    schedule.run();
+///    sleep(Duration::from_millis(100));
+/// }
+/// ```
+pub struct SystemdUnit {
+    pub unit_name: String,
+}
+
+impl SystemdUnit {
+    pub fn new(unit_name: &str) -> SystemdUnit {
+        SystemdUnit {
+            unit_name: unit_name.to_string(),
+        }
+    }
+
+    /// The unit's current `ActiveState` (e.g. `"active"`, `"failed"`,
+    /// `"inactive"`), as reported by `systemctl show`.
+    fn active_state(&self) -> Option<String> {
+        let output = Command::new("systemctl")
+            .args(["show", "--property=ActiveState", "--value", &self.unit_name])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let state = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if state.is_empty() {
+            None
+        } else {
+            Some(state)
+        }
+    }
+}
+
+impl StateMatcher for SystemdUnit {}
+
+/// Checks whether a systemd unit's active-state is `active`.
+impl Monitor for SystemdUnit {
+    fn check(&mut self) -> Action {
+        match self.active_state() {
+            Some(state) if state == "active" => {
+                info!("{} is active", self.unit_name);
+                Action::Nothing
+            }
+            Some(state) => {
+                let diagnostic = format!("{} is {state}", self.unit_name);
+                info!("{diagnostic}");
+                Action::Notify {
+                    diagnostic: Some(diagnostic),
+                }
+            }
+            None => {
+                let diagnostic = format!("{} could not be queried", self.unit_name);
+                info!("{diagnostic}");
+                Action::Notify {
+                    diagnostic: Some(diagnostic),
+                }
+            }
+        }
+    }
+}
+
+/// Notifies systemd's service supervisor (`Type=notify`) of readiness and
+/// liveness, so systemd can restart the monitor itself if it hangs.
+///
+/// Reads `NOTIFY_SOCKET` once at construction and becomes a no-op if it isn't
+/// set, so it's safe to construct unconditionally even when not running under
+/// systemd.
+///
+/// # Example
+///
+/// ```
+/// # use std::thread::sleep;
+/// # use std::time::Duration;
+/// use gargoyle::Schedule;
+/// use gargoyle::modules::monitor::systemd::Watchdog;
+/// let watchdog = Watchdog::from_env();
+/// watchdog.notify_ready();
+/// let mut schedule = Schedule::new();
+/// loop {
+///    schedule.run();
+///    watchdog.notify_alive();
+///    sleep(Duration::from_millis(100));
+/// }
+/// ```
+pub struct Watchdog {
+    socket: Option<UnixDatagram>,
+}
+
+impl Watchdog {
+    /// Read `NOTIFY_SOCKET` from the environment and connect to it, if set.
+    ///
+    /// A leading `@` denotes an abstract-namespace address (common under
+    /// systemd in containers/user sessions, per `sd_notify(3)`) rather than a
+    /// filesystem path, and is translated accordingly.
+    pub fn from_env() -> Watchdog {
+        let socket = env::var("NOTIFY_SOCKET").ok().and_then(|path| {
+            let socket = match UnixDatagram::unbound() {
+                Ok(socket) => socket,
+                Err(err) => {
+                    info!("failed to create watchdog socket: {err}");
+                    return None;
+                }
+            };
+
+            let connected = if let Some(name) = path.strip_prefix('@') {
+                SocketAddr::from_abstract_name(name.as_bytes())
+                    .and_then(|addr| socket.connect_addr(&addr))
+            } else {
+                socket.connect(&path)
+            };
+
+            if let Err(err) = connected {
+                info!("failed to connect to NOTIFY_SOCKET {path}: {err}");
+                return None;
+            }
+
+            Some(socket)
+        });
+        Watchdog { socket }
+    }
+
+    fn send(&self, message: &str) {
+        if let Some(socket) = &self.socket {
+            if let Err(err) = socket.send(message.as_bytes()) {
+                info!("failed to notify systemd ({message}): {err}");
+            }
+        }
+    }
+
+    /// Send `READY=1`, telling systemd the service has finished starting up.
+    /// Call this once, after setup and before entering the monitoring loop.
+    pub fn notify_ready(&self) {
+        self.send("READY=1");
+    }
+
+    /// Send `WATCHDOG=1`, telling systemd the service is still alive. Call
+    /// this once per successful pass through the monitoring loop.
+    pub fn notify_alive(&self) {
+        self.send("WATCHDOG=1");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watchdog_from_env_is_a_no_op_when_notify_socket_is_unset() {
+        let previous = env::var_os("NOTIFY_SOCKET");
+        env::remove_var("NOTIFY_SOCKET");
+
+        let watchdog = Watchdog::from_env();
+        assert!(watchdog.socket.is_none());
+        // Neither call should panic without a connected socket.
+        watchdog.notify_ready();
+        watchdog.notify_alive();
+
+        if let Some(previous) = previous {
+            env::set_var("NOTIFY_SOCKET", previous);
+        }
+    }
+}